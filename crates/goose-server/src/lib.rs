@@ -0,0 +1,3 @@
+pub mod redaction;
+pub mod routes;
+pub mod state;
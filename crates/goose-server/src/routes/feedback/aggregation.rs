@@ -0,0 +1,268 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use tracing::debug;
+
+use crate::redaction::ScrubberSet;
+use crate::state::AppState;
+
+use super::{
+    file_github_issue, log_failure_report_locally, publish_report, FailureReportRequest, W3cReport,
+};
+
+/// Common shape both the native `/report-failure` payload and the W3C
+/// Reporting API payload normalize into, so a single pipeline can log,
+/// deduplicate, and file GitHub issues regardless of where a report came
+/// from.
+#[derive(Debug, Clone)]
+pub struct NormalizedFailure {
+    pub title: String,
+    pub description: String,
+    pub context: String,
+    pub recent_errors: Vec<String>,
+    pub timestamp: String,
+    pub provider_type: Option<String>,
+}
+
+impl From<&FailureReportRequest> for NormalizedFailure {
+    fn from(payload: &FailureReportRequest) -> Self {
+        NormalizedFailure {
+            title: payload.title.clone(),
+            description: payload.description.clone(),
+            context: format!(
+                "Goose {version} on {os_version} ({platform}/{architecture}), provider: {provider}, {extensions} extensions loaded",
+                version = payload.system_info.goose_version,
+                os_version = payload.system_info.os_version,
+                platform = payload.system_info.platform,
+                architecture = payload.system_info.architecture,
+                provider = payload.system_info.provider_type.as_deref().unwrap_or("unknown"),
+                extensions = payload.system_info.extension_count,
+            ),
+            recent_errors: payload.recent_errors.clone(),
+            timestamp: payload.timestamp.clone(),
+            provider_type: payload.system_info.provider_type.clone(),
+        }
+    }
+}
+
+impl From<&W3cReport> for NormalizedFailure {
+    fn from(report: &W3cReport) -> Self {
+        let occurred_at = SystemTime::now()
+            .checked_sub(Duration::from_millis(report.age))
+            .unwrap_or(UNIX_EPOCH)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        NormalizedFailure {
+            title: format!("{} report: {}", report.report_type, report.url),
+            description: serde_json::to_string_pretty(&report.body).unwrap_or_default(),
+            context: format!("{} via {}", report.url, report.user_agent),
+            recent_errors: Vec::new(),
+            timestamp: occurred_at.to_string(),
+            provider_type: None,
+        }
+    }
+}
+
+impl NormalizedFailure {
+    /// Scrubs secret-shaped substrings (bearer tokens, provider API keys,
+    /// home-directory paths, emails) out of the free-text fields. Must run
+    /// before a report is logged, stored, or forwarded to GitHub, since crash
+    /// descriptions and error traces frequently contain them verbatim.
+    pub fn redact(mut self, scrubbers: &ScrubberSet) -> Self {
+        self.description = scrubbers.scrub(&self.description);
+        self.context = scrubbers.scrub(&self.context);
+        self.recent_errors = self
+            .recent_errors
+            .iter()
+            .map(|error| scrubbers.scrub(error))
+            .collect();
+        self
+    }
+}
+
+/// A fingerprint collapsed over a time window, tracking how many times it has
+/// been seen so repeat W3C reports don't each trigger their own GitHub issue.
+#[derive(Debug, Clone)]
+pub struct AggregatedReport {
+    failure: NormalizedFailure,
+    first_seen: Instant,
+    last_seen: Instant,
+    count: u32,
+}
+
+/// Fingerprints a W3C report from its `type` plus a normalized signature of
+/// its `body`, so repeated occurrences of the same underlying failure
+/// collapse into one aggregate. `serde_json::Value`'s default map keeps keys
+/// sorted, so the signature is stable regardless of field order.
+fn w3c_fingerprint(report: &W3cReport) -> String {
+    let mut hasher = DefaultHasher::new();
+    report.report_type.hash(&mut hasher);
+    report.body.to_string().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Records a W3C report against its fingerprint's aggregate, flushing the
+/// aggregate to the logging/GitHub pipeline once its window has elapsed or
+/// it has been seen `aggregation_config.flush_threshold` times.
+pub async fn record_w3c_report(state: &AppState, report: &W3cReport) {
+    let fingerprint = w3c_fingerprint(report);
+    let flushed = {
+        let mut aggregates = state.report_aggregator.lock().unwrap();
+        let now = Instant::now();
+        let window = state.aggregation_config.window;
+        let threshold = state.aggregation_config.flush_threshold;
+
+        let entry = aggregates
+            .entry(fingerprint.clone())
+            .or_insert_with(|| AggregatedReport {
+                failure: NormalizedFailure::from(report).redact(&state.scrubbers),
+                first_seen: now,
+                last_seen: now,
+                count: 0,
+            });
+        entry.last_seen = now;
+        entry.count += 1;
+
+        if now.duration_since(entry.first_seen) >= window || entry.count >= threshold {
+            aggregates.remove(&fingerprint)
+        } else {
+            None
+        }
+    };
+
+    if let Some(aggregate) = flushed {
+        flush_aggregate(state, &fingerprint, aggregate).await;
+    }
+}
+
+async fn flush_aggregate(state: &AppState, fingerprint: &str, aggregate: AggregatedReport) {
+    debug!(
+        "Flushing aggregated report {} ({} occurrence(s) over {:?})",
+        fingerprint,
+        aggregate.count,
+        aggregate.last_seen.duration_since(aggregate.first_seen),
+    );
+
+    log_failure_report_locally(&aggregate.failure);
+    publish_report(state, fingerprint, &aggregate.failure);
+    // Each aggregate has already collapsed every repeat of this fingerprint
+    // seen during its window, so there's nothing else in this single flush a
+    // cache could dedupe against.
+    file_github_issue(state, &aggregate.failure, &mut HashMap::new()).await;
+}
+
+/// Periodically sweeps aggregates whose window has elapsed without a new
+/// occurrence, so a failure that stops recurring still gets flushed instead
+/// of sitting in memory forever.
+pub fn spawn_aggregation_sweeper(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(5));
+        loop {
+            ticker.tick().await;
+            sweep_expired_aggregates(&state).await;
+        }
+    });
+}
+
+async fn sweep_expired_aggregates(state: &AppState) {
+    let expired: Vec<(String, AggregatedReport)> = {
+        let mut aggregates = state.report_aggregator.lock().unwrap();
+        let now = Instant::now();
+        let window = state.aggregation_config.window;
+        let expired_keys: Vec<String> = aggregates
+            .iter()
+            .filter(|(_, aggregate)| now.duration_since(aggregate.first_seen) >= window)
+            .map(|(fingerprint, _)| fingerprint.clone())
+            .collect();
+
+        expired_keys
+            .into_iter()
+            .filter_map(|fingerprint| {
+                aggregates
+                    .remove(&fingerprint)
+                    .map(|aggregate| (fingerprint, aggregate))
+            })
+            .collect()
+    };
+
+    for (fingerprint, aggregate) in expired {
+        flush_aggregate(state, &fingerprint, aggregate).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ReportAggregationConfig;
+
+    fn w3c_report(report_type: &str) -> W3cReport {
+        W3cReport {
+            report_type: report_type.to_string(),
+            url: "https://example.com/app".to_string(),
+            age: 0,
+            user_agent: "test-agent".to_string(),
+            body: serde_json::json!({"message": "boom"}),
+        }
+    }
+
+    fn state_with_config(window: Duration, flush_threshold: u32) -> AppState {
+        let mut state = AppState::new(None);
+        state.aggregation_config = ReportAggregationConfig {
+            window,
+            flush_threshold,
+        };
+        state
+    }
+
+    #[tokio::test]
+    async fn does_not_flush_before_threshold_or_window_elapsed() {
+        let state = state_with_config(Duration::from_secs(60), 3);
+
+        record_w3c_report(&state, &w3c_report("crash")).await;
+
+        let aggregates = state.report_aggregator.lock().unwrap();
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(aggregates.values().next().unwrap().count, 1);
+    }
+
+    #[tokio::test]
+    async fn flushes_once_threshold_is_reached() {
+        let state = state_with_config(Duration::from_secs(60), 3);
+        let report = w3c_report("crash");
+
+        record_w3c_report(&state, &report).await;
+        record_w3c_report(&state, &report).await;
+        record_w3c_report(&state, &report).await;
+
+        let aggregates = state.report_aggregator.lock().unwrap();
+        assert!(aggregates.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sweep_does_not_flush_aggregates_whose_window_has_not_elapsed() {
+        let state = state_with_config(Duration::from_secs(60), 1000);
+
+        record_w3c_report(&state, &w3c_report("crash")).await;
+        sweep_expired_aggregates(&state).await;
+
+        let aggregates = state.report_aggregator.lock().unwrap();
+        assert_eq!(aggregates.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sweep_flushes_aggregates_whose_window_has_elapsed() {
+        let state = state_with_config(Duration::from_millis(10), 1000);
+
+        record_w3c_report(&state, &w3c_report("crash")).await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        sweep_expired_aggregates(&state).await;
+
+        let aggregates = state.report_aggregator.lock().unwrap();
+        assert!(aggregates.is_empty());
+    }
+}
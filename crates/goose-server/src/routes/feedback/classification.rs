@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::NormalizedFailure;
+
+/// A machine-readable bucket for a failure, derived by pattern-matching its
+/// recent errors and provider type. Drives triage (e.g. routing provider-auth
+/// failures differently from extension crashes) without a human reading the
+/// description first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailureCategory {
+    ProviderAuth,
+    ExtensionLoad,
+    NetworkTimeout,
+    Serialization,
+    Unknown,
+}
+
+/// Mirrors the internal `ReportableError` pattern used elsewhere for typed
+/// errors: something that can describe itself with a machine-readable
+/// category plus free-form extra context for the failure/issue pipeline.
+pub trait ReportableError {
+    fn category(&self) -> FailureCategory;
+    fn extras(&self) -> HashMap<String, String>;
+}
+
+impl ReportableError for NormalizedFailure {
+    fn category(&self) -> FailureCategory {
+        classify(self)
+    }
+
+    fn extras(&self) -> HashMap<String, String> {
+        let mut extras = HashMap::new();
+
+        if let Some(provider) = &self.provider_type {
+            extras.insert("provider_type".to_string(), provider.clone());
+        }
+
+        if classify(self) == FailureCategory::Unknown {
+            // Capture the raw payload verbatim so unrecognized failure shapes
+            // are still actionable, mirroring how upstream response parsing
+            // falls back to the raw bytes when JSON decoding fails. Reports
+            // without recent_errors (e.g. W3C ingestion) still have their raw
+            // shape captured via description.
+            let raw = if self.recent_errors.is_empty() {
+                self.description.clone()
+            } else {
+                self.recent_errors.join("\n")
+            };
+            extras.insert("raw_recent_errors".to_string(), raw);
+        }
+
+        extras
+    }
+}
+
+/// Pattern-matches a failure's recent errors plus description for
+/// recognizable signatures. `recent_errors` is the native `/report-failure`
+/// pipeline's source of truth, but W3C reports (chunk0-3) never populate it —
+/// their payload lives in `description` instead — so both are searched to
+/// keep classification working across either ingestion path.
+fn classify(failure: &NormalizedFailure) -> FailureCategory {
+    let haystack = format!("{}\n{}", failure.recent_errors.join("\n"), failure.description)
+        .to_lowercase();
+
+    if haystack.trim().is_empty() {
+        return FailureCategory::Unknown;
+    }
+
+    if ["401", "unauthorized", "invalid api key", "invalid_api_key"]
+        .iter()
+        .any(|needle| haystack.contains(needle))
+    {
+        FailureCategory::ProviderAuth
+    } else if haystack.contains("extension")
+        && ["failed to start", "failed to load", "spawn"]
+            .iter()
+            .any(|needle| haystack.contains(needle))
+    {
+        FailureCategory::ExtensionLoad
+    } else if ["timed out", "timeout", "connection refused", "econnrefused"]
+        .iter()
+        .any(|needle| haystack.contains(needle))
+    {
+        FailureCategory::NetworkTimeout
+    } else if ["serde", "deserialize", "unexpected token", "invalid json"]
+        .iter()
+        .any(|needle| haystack.contains(needle))
+    {
+        FailureCategory::Serialization
+    } else {
+        FailureCategory::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(recent_errors: &[&str], provider_type: Option<&str>) -> NormalizedFailure {
+        NormalizedFailure {
+            title: "Something went wrong".to_string(),
+            description: "a failure occurred".to_string(),
+            context: "test context".to_string(),
+            recent_errors: recent_errors.iter().map(|s| s.to_string()).collect(),
+            timestamp: "2026-07-26T00:00:00Z".to_string(),
+            provider_type: provider_type.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn classifies_provider_auth_errors() {
+        let failure = failure(&["401 Unauthorized: invalid API key"], Some("anthropic"));
+        assert_eq!(failure.category(), FailureCategory::ProviderAuth);
+    }
+
+    #[test]
+    fn classifies_extension_load_errors() {
+        let failure = failure(&["Extension 'developer' failed to start: spawn ENOENT"], None);
+        assert_eq!(failure.category(), FailureCategory::ExtensionLoad);
+    }
+
+    #[test]
+    fn classifies_network_timeout_errors() {
+        let failure = failure(&["request to provider timed out after 30s"], None);
+        assert_eq!(failure.category(), FailureCategory::NetworkTimeout);
+    }
+
+    #[test]
+    fn classifies_serialization_errors() {
+        let failure = failure(&["failed to deserialize response: unexpected token at line 1"], None);
+        assert_eq!(failure.category(), FailureCategory::Serialization);
+    }
+
+    #[test]
+    fn classifies_unrecognized_errors_as_unknown() {
+        let failure = failure(&["the goose has wandered off"], None);
+        assert_eq!(failure.category(), FailureCategory::Unknown);
+    }
+
+    #[test]
+    fn unknown_category_captures_raw_errors_as_extra() {
+        let failure = failure(&["the goose has wandered off"], None);
+        let extras = failure.extras();
+        assert_eq!(
+            extras.get("raw_recent_errors"),
+            Some(&"the goose has wandered off".to_string())
+        );
+    }
+
+    #[test]
+    fn known_category_omits_raw_errors_extra() {
+        let failure = failure(&["401 Unauthorized"], None);
+        let extras = failure.extras();
+        assert!(!extras.contains_key("raw_recent_errors"));
+    }
+
+    #[test]
+    fn extras_includes_provider_type_when_present() {
+        let failure = failure(&["401 Unauthorized"], Some("openai"));
+        let extras = failure.extras();
+        assert_eq!(extras.get("provider_type"), Some(&"openai".to_string()));
+    }
+
+    #[test]
+    fn classifies_using_description_when_recent_errors_is_empty() {
+        // Mirrors a W3C report: recent_errors is always empty, the real
+        // payload lives in description.
+        let mut failure = failure(&[], None);
+        failure.description = "401 unauthorized invalid api key".to_string();
+        assert_eq!(failure.category(), FailureCategory::ProviderAuth);
+    }
+
+    #[test]
+    fn unknown_category_falls_back_to_description_for_raw_extra_when_recent_errors_is_empty() {
+        let mut failure = failure(&[], None);
+        failure.description = "the goose has wandered off".to_string();
+        let extras = failure.extras();
+        assert_eq!(
+            extras.get("raw_recent_errors"),
+            Some(&"the goose has wandered off".to_string())
+        );
+    }
+}
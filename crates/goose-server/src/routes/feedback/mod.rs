@@ -0,0 +1,464 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::state::{AppState, GitHubConfig};
+
+use aggregation::record_w3c_report;
+use classification::ReportableError;
+use stream::publish_report;
+
+pub use aggregation::{spawn_aggregation_sweeper, AggregatedReport, NormalizedFailure};
+pub use classification::FailureCategory;
+pub use stream::BroadcastReport;
+
+mod aggregation;
+mod classification;
+mod stream;
+
+/// Marker embedded in the body of filed issues so future reports with the same
+/// fingerprint can be found again via the GitHub search API instead of opening
+/// a duplicate.
+const FINGERPRINT_MARKER_PREFIX: &str = "<!-- goose-failure-fingerprint:";
+
+#[derive(Debug, Deserialize)]
+struct SystemInfo {
+    #[serde(rename = "gooseVersion")]
+    goose_version: String,
+    #[serde(rename = "osVersion")]
+    os_version: String,
+    platform: String,
+    architecture: String,
+    #[serde(rename = "providerType")]
+    provider_type: Option<String>,
+    #[serde(rename = "extensionCount")]
+    extension_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct FailureReportRequest {
+    title: String,
+    description: String,
+    #[serde(rename = "systemInfo")]
+    system_info: SystemInfo,
+    #[serde(rename = "recentErrors")]
+    recent_errors: Vec<String>,
+    timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+struct FailureReportResponse {
+    success: bool,
+    message: String,
+    #[serde(rename = "reportIDs")]
+    report_ids: Vec<String>,
+    classifications: Vec<ReportClassification>,
+}
+
+/// The category and extra context classification derived for a single
+/// report, returned so a client can triage without re-parsing errors itself.
+#[derive(Debug, Serialize)]
+struct ReportClassification {
+    #[serde(rename = "reportID")]
+    report_id: String,
+    category: FailureCategory,
+    extras: std::collections::HashMap<String, String>,
+}
+
+/// A `/report-failure` body may be a single report or a batch, so a desktop
+/// client can flush a queue of errors accumulated while offline in one POST
+/// instead of one per crash.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PostReportsPayload {
+    Single(FailureReportRequest),
+    Multiple(Vec<FailureReportRequest>),
+}
+
+impl PostReportsPayload {
+    fn into_reports(self) -> Vec<FailureReportRequest> {
+        match self {
+            PostReportsPayload::Single(report) => vec![report],
+            PostReportsPayload::Multiple(reports) => reports,
+        }
+    }
+}
+
+/// A report in the W3C Reporting API format (`Content-Type:
+/// application/reports+json`), e.g. as emitted by a browser/webview runtime
+/// for CSP violations, deprecations, or crashes.
+#[derive(Debug, Deserialize)]
+struct W3cReport {
+    #[serde(rename = "type")]
+    report_type: String,
+    url: String,
+    age: u64,
+    user_agent: String,
+    body: serde_json::Value,
+}
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    spawn_aggregation_sweeper(state.clone());
+
+    Router::new()
+        .route("/report-failure", post(report_failure))
+        .route("/report-failure/stream", get(stream::stream_reports))
+        .route("/reports", post(ingest_w3c_reports))
+        .with_state(state)
+}
+
+async fn report_failure(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<PostReportsPayload>,
+) -> Result<Json<FailureReportResponse>, StatusCode> {
+    let reports = payload.into_reports();
+    let mut report_ids = Vec::with_capacity(reports.len());
+    let mut classifications = Vec::with_capacity(reports.len());
+
+    // GitHub's search API is only eventually consistent, so an issue filed
+    // for the 1st report in a batch may not be visible yet when the 2nd
+    // identical report (e.g. the same crash repeated while offline) is
+    // processed moments later. Track fingerprints already handled in this
+    // batch and reuse that result instead of re-querying GitHub for repeats.
+    let mut batch_issue_cache: HashMap<String, GitHubIssue> = HashMap::new();
+
+    for report in &reports {
+        let report_id = Uuid::new_v4().to_string();
+        let normalized = NormalizedFailure::from(report).redact(&state.scrubbers);
+        let category = normalized.category();
+        let extras = normalized.extras();
+
+        info!(
+            "Received failure report {}: {} - {} ({:?})",
+            report_id,
+            normalized.title,
+            normalized.description.chars().take(100).collect::<String>(),
+            category,
+        );
+        debug!("Context: {}", normalized.context);
+
+        log_failure_report_locally(&normalized);
+        publish_report(&state, &report_id, &normalized);
+
+        if let Some(issue_url) =
+            file_github_issue(&state, &normalized, &mut batch_issue_cache).await
+        {
+            debug!("Filed GitHub issue {} for report {}", issue_url, report_id);
+        }
+
+        classifications.push(ReportClassification {
+            report_id: report_id.clone(),
+            category,
+            extras,
+        });
+        report_ids.push(report_id);
+    }
+
+    Ok(Json(FailureReportResponse {
+        success: true,
+        message: format!("Logged {} failure report(s)", report_ids.len()),
+        report_ids,
+        classifications,
+    }))
+}
+
+/// Accepts the standardized W3C Reporting API format. The browser/webview
+/// runtime posts these as `Content-Type: application/reports+json`, which
+/// `axum::Json` won't accept, so the body is parsed manually.
+async fn ingest_w3c_reports(State(state): State<Arc<AppState>>, body: Bytes) -> StatusCode {
+    let reports: Vec<W3cReport> = match serde_json::from_slice(&body) {
+        Ok(reports) => reports,
+        Err(e) => {
+            error!("Failed to parse W3C reporting payload: {}", e);
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    for report in &reports {
+        record_w3c_report(&state, report).await;
+    }
+
+    StatusCode::NO_CONTENT
+}
+
+/// Computes a stable fingerprint for a report so repeat occurrences of the
+/// same failure can be deduplicated against an already-filed GitHub issue.
+///
+/// This fingerprint is embedded in filed issue bodies and matched against on
+/// every future report, so it must stay stable across process restarts and
+/// toolchain upgrades — `std::hash::Hasher` (e.g. `DefaultHasher`) is
+/// explicitly *not* guaranteed to be, so a fixed-algorithm hash is used
+/// instead.
+fn failure_fingerprint(failure: &NormalizedFailure) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(failure.title.trim().to_lowercase().as_bytes());
+    hasher.update([0]);
+    hasher.update(failure.provider_type.as_deref().unwrap_or("").as_bytes());
+    hasher.update([0]);
+    hasher.update(failure.recent_errors.join("\n").as_bytes());
+    hex_prefix(&hasher.finalize(), 16)
+}
+
+/// Renders the first `len` bytes of a digest as lowercase hex. Truncating
+/// keeps fingerprints short enough to embed in a GitHub search query while
+/// leaving far more bits than needed to avoid collisions in practice.
+fn hex_prefix(digest: &[u8], len: usize) -> String {
+    digest
+        .iter()
+        .take(len)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GitHubIssue {
+    number: u64,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubSearchResponse {
+    items: Vec<GitHubIssue>,
+}
+
+fn issue_body(failure: &NormalizedFailure, fingerprint: &str) -> String {
+    let extras = failure
+        .extras()
+        .into_iter()
+        .map(|(key, value)| format!("- {}: {}", key, value))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "{description}\n\n\
+        ### Context\n\
+        {context}\n\
+        - Category: {category:?}\n\
+        {extras}\n\
+        - Reported at: {timestamp}\n\n\
+        <details><summary>Recent errors</summary>\n\n\
+        ```\n{recent_errors}\n```\n\
+        </details>\n\n\
+        {marker} {fingerprint} -->",
+        description = failure.description,
+        context = failure.context,
+        category = failure.category(),
+        extras = extras,
+        timestamp = failure.timestamp,
+        recent_errors = failure.recent_errors.join("\n"),
+        marker = FINGERPRINT_MARKER_PREFIX,
+        fingerprint = fingerprint,
+    )
+}
+
+async fn find_existing_issue(
+    client: &Client,
+    github: &GitHubConfig,
+    fingerprint: &str,
+) -> Result<Option<GitHubIssue>, reqwest::Error> {
+    let query = format!(
+        "repo:{owner}/{repo} is:issue is:open in:body \"{marker} {fingerprint}\"",
+        owner = github.owner,
+        repo = github.repo,
+        marker = FINGERPRINT_MARKER_PREFIX,
+        fingerprint = fingerprint,
+    );
+
+    let response = client
+        .get("https://api.github.com/search/issues")
+        .bearer_auth(github.token.expose())
+        .header("User-Agent", "goose-server")
+        .query(&[("q", query)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<GitHubSearchResponse>()
+        .await?;
+
+    Ok(response.items.into_iter().next())
+}
+
+async fn comment_on_issue(
+    client: &Client,
+    github: &GitHubConfig,
+    issue_number: u64,
+    failure: &NormalizedFailure,
+) -> Result<(), reqwest::Error> {
+    let url = format!(
+        "https://api.github.com/repos/{owner}/{repo}/issues/{issue_number}/comments",
+        owner = github.owner,
+        repo = github.repo,
+        issue_number = issue_number,
+    );
+
+    client
+        .post(url)
+        .bearer_auth(github.token.expose())
+        .header("User-Agent", "goose-server")
+        .json(&serde_json::json!({
+            "body": format!("Seen again at {timestamp}.", timestamp = failure.timestamp),
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+async fn create_github_issue(
+    client: &Client,
+    github: &GitHubConfig,
+    failure: &NormalizedFailure,
+    fingerprint: &str,
+) -> Result<GitHubIssue, reqwest::Error> {
+    let url = format!(
+        "https://api.github.com/repos/{owner}/{repo}/issues",
+        owner = github.owner,
+        repo = github.repo,
+    );
+
+    client
+        .post(url)
+        .bearer_auth(github.token.expose())
+        .header("User-Agent", "goose-server")
+        .json(&serde_json::json!({
+            "title": failure.title,
+            "body": issue_body(failure, fingerprint),
+        }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<GitHubIssue>()
+        .await
+}
+
+/// Files a GitHub issue for the report, or bumps an existing issue with the
+/// same fingerprint. Returns `None` (never an error) when GitHub integration
+/// isn't configured or the API call fails, since the report has already been
+/// logged locally and should never be lost.
+///
+/// `batch_cache` short-circuits the GitHub search lookup for a fingerprint
+/// already seen earlier in the same batch/sweep, since GitHub's search API
+/// is only eventually consistent and won't yet reflect an issue this same
+/// call created moments ago.
+async fn file_github_issue(
+    state: &AppState,
+    failure: &NormalizedFailure,
+    batch_cache: &mut HashMap<String, GitHubIssue>,
+) -> Option<String> {
+    let github = state.github.as_ref()?;
+    let fingerprint = failure_fingerprint(failure);
+
+    if let Some(issue) = batch_cache.get(&fingerprint) {
+        if let Err(e) = comment_on_issue(&state.http_client, github, issue.number, failure).await {
+            error!(
+                "Failed to comment on existing GitHub issue #{}: {}",
+                issue.number, e
+            );
+        }
+        return Some(issue.html_url.clone());
+    }
+
+    let issue = match find_existing_issue(&state.http_client, github, &fingerprint).await {
+        Ok(Some(issue)) => {
+            if let Err(e) =
+                comment_on_issue(&state.http_client, github, issue.number, failure).await
+            {
+                error!(
+                    "Failed to comment on existing GitHub issue #{}: {}",
+                    issue.number, e
+                );
+            }
+            Some(issue)
+        }
+        Ok(None) => {
+            match create_github_issue(&state.http_client, github, failure, &fingerprint).await {
+                Ok(issue) => Some(issue),
+                Err(e) => {
+                    error!("Failed to create GitHub issue: {}", e);
+                    None
+                }
+            }
+        }
+        Err(e) => {
+            error!("Failed to search for existing GitHub issue: {}", e);
+            None
+        }
+    }?;
+
+    let issue_url = issue.html_url.clone();
+    batch_cache.insert(fingerprint, issue);
+    Some(issue_url)
+}
+
+fn log_failure_report_locally(failure: &NormalizedFailure) {
+    // Log the failure report for manual processing
+    error!(
+        "FAILURE REPORT (Manual Processing Required): Title: {}, Description: {}, Context: {}, Timestamp: {}",
+        failure.title, failure.description, failure.context, failure.timestamp
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(title: &str, recent_errors: &[&str], provider_type: Option<&str>) -> NormalizedFailure {
+        NormalizedFailure {
+            title: title.to_string(),
+            description: "a failure occurred".to_string(),
+            context: "test context".to_string(),
+            recent_errors: recent_errors.iter().map(|s| s.to_string()).collect(),
+            timestamp: "2026-07-26T00:00:00Z".to_string(),
+            provider_type: provider_type.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn fingerprint_is_stable_for_identical_reports() {
+        let a = failure("Extension failed to load", &["boom"], Some("anthropic"));
+        let b = failure("Extension failed to load", &["boom"], Some("anthropic"));
+        assert_eq!(failure_fingerprint(&a), failure_fingerprint(&b));
+    }
+
+    #[test]
+    fn fingerprint_is_case_and_whitespace_insensitive_on_title() {
+        let a = failure("  Extension Failed To Load  ", &["boom"], Some("anthropic"));
+        let b = failure("extension failed to load", &["boom"], Some("anthropic"));
+        assert_eq!(failure_fingerprint(&a), failure_fingerprint(&b));
+    }
+
+    #[test]
+    fn fingerprint_differs_for_unrelated_errors_sharing_a_title() {
+        let a = failure(
+            "Extension failed to load",
+            &["developer extension: ENOENT"],
+            Some("anthropic"),
+        );
+        let b = failure(
+            "Extension failed to load",
+            &["memory extension: permission denied"],
+            Some("anthropic"),
+        );
+        assert_ne!(failure_fingerprint(&a), failure_fingerprint(&b));
+    }
+
+    #[test]
+    fn hex_prefix_truncates_and_formats_lowercase_hex() {
+        let digest = [0xAB, 0x01, 0xFF];
+        assert_eq!(hex_prefix(&digest, 2), "ab01");
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::state::AppState;
+
+use super::NormalizedFailure;
+
+/// How many recently-seen reports are kept in memory so a freshly opened
+/// `/report-failure/stream` connection can replay recent history instead of
+/// starting blank.
+pub const RECENT_REPORTS_CAPACITY: usize = 50;
+
+/// A redacted report as broadcast to `/report-failure/stream` subscribers.
+/// `sequence` doubles as the SSE event id, letting a client resume with
+/// `Last-Event-ID` (or `?since=`) after a dropped connection.
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastReport {
+    pub sequence: u64,
+    pub report_id: String,
+    pub title: String,
+    pub description: String,
+    pub context: String,
+    pub recent_errors: Vec<String>,
+    pub timestamp: String,
+}
+
+/// Publishes an already-redacted report to the live stream and appends it to
+/// the replay buffer. Called once a report (or a flushed aggregate) has been
+/// logged/filed, so the dashboard only ever sees what was actually recorded.
+pub fn publish_report(state: &AppState, report_id: &str, failure: &NormalizedFailure) {
+    let sequence = state.report_sequence.fetch_add(1, Ordering::Relaxed);
+    let report = BroadcastReport {
+        sequence,
+        report_id: report_id.to_string(),
+        title: failure.title.clone(),
+        description: failure.description.clone(),
+        context: failure.context.clone(),
+        recent_errors: failure.recent_errors.clone(),
+        timestamp: failure.timestamp.clone(),
+    };
+
+    {
+        let mut recent = state.recent_reports.lock().unwrap();
+        if recent.len() >= RECENT_REPORTS_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(report.clone());
+    }
+
+    // No active subscribers is not an error; the report is still buffered.
+    let _ = state.report_broadcaster.send(report);
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    since: Option<u64>,
+}
+
+fn last_event_id(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+pub async fn stream_reports(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<StreamQuery>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let since = query.since.or_else(|| last_event_id(&headers));
+
+    // Subscribe before snapshotting the replay buffer so a report published
+    // in between (mod.rs's `publish_report` pushes to `recent_reports` then
+    // broadcasts) is never dropped into the gap between the two. The same
+    // report can now show up in both the snapshot and the live channel, so
+    // live events up to the last replayed sequence are filtered back out.
+    let subscription = state.report_broadcaster.subscribe();
+
+    let replay: Vec<BroadcastReport> = {
+        let recent = state.recent_reports.lock().unwrap();
+        recent
+            .iter()
+            .filter(|report| since.is_none_or(|since| report.sequence > since))
+            .cloned()
+            .collect()
+    };
+
+    let last_replayed_sequence = replay.last().map(|report| report.sequence);
+    let live = BroadcastStream::new(subscription)
+        .filter_map(|result| async move { result.ok() })
+        .filter(move |report| {
+            let keep = last_replayed_sequence.is_none_or(|last| report.sequence > last);
+            futures::future::ready(keep)
+        });
+
+    let events = stream::iter(replay)
+        .chain(live)
+        .map(|report| Event::default().id(report.sequence.to_string()).json_data(&report));
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,70 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::AtomicU64;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::Client;
+use tokio::sync::broadcast;
+
+use crate::redaction::{Redacted, ScrubberSet};
+use crate::routes::feedback::{AggregatedReport, BroadcastReport};
+
+/// Bounded so a burst of reports can't grow the live-stream channel
+/// unboundedly; slow subscribers just miss the oldest ones (`Lagged`), which
+/// `BroadcastStream` surfaces as a skipped event rather than a crash.
+const BROADCAST_CHANNEL_CAPACITY: usize = 256;
+
+/// Credentials and target repo for filing GitHub issues from failure reports.
+///
+/// When absent, failure reports are only ever logged locally.
+#[derive(Clone)]
+pub struct GitHubConfig {
+    pub owner: String,
+    pub repo: String,
+    pub token: Redacted<String>,
+}
+
+/// Controls how long W3C reports with the same fingerprint are collapsed
+/// into a single aggregate before being flushed to the logging/GitHub
+/// pipeline.
+pub struct ReportAggregationConfig {
+    pub window: Duration,
+    pub flush_threshold: u32,
+}
+
+impl Default for ReportAggregationConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(60),
+            flush_threshold: 20,
+        }
+    }
+}
+
+pub struct AppState {
+    pub http_client: Client,
+    pub github: Option<GitHubConfig>,
+    pub report_aggregator: Mutex<HashMap<String, AggregatedReport>>,
+    pub aggregation_config: ReportAggregationConfig,
+    pub scrubbers: ScrubberSet,
+    pub report_broadcaster: broadcast::Sender<BroadcastReport>,
+    pub recent_reports: Mutex<VecDeque<BroadcastReport>>,
+    pub report_sequence: AtomicU64,
+}
+
+impl AppState {
+    pub fn new(github: Option<GitHubConfig>) -> Self {
+        let (report_broadcaster, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+
+        Self {
+            http_client: Client::new(),
+            github,
+            report_aggregator: Mutex::new(HashMap::new()),
+            aggregation_config: ReportAggregationConfig::default(),
+            scrubbers: ScrubberSet::default(),
+            report_broadcaster,
+            recent_reports: Mutex::new(VecDeque::new()),
+            report_sequence: AtomicU64::new(0),
+        }
+    }
+}